@@ -1,16 +1,68 @@
 use proc_macro2::TokenStream;
 use quote::ToTokens;
-use syn::{Block, FnArg, Ident, ItemFn, Pat, parse_quote, spanned::Spanned};
+use syn::{
+    Block, Expr, FnArg, Ident, ItemFn, LitStr, Pat, Token, parse::Parse, parse::ParseStream,
+    parse_quote, spanned::Spanned,
+};
 
 use crate::utils::crate_name;
 
 /// Default variable name for the `rstest::Context` binding in the generated code.
 const DEFAULT_CTX_BINDING: &str = "__rstest_insta__ctx";
 
-pub fn expand(input_fn: ItemFn) -> TokenStream {
+/// Arguments parsed from `#[rstest_insta(...)]`.
+///
+/// They configure how the snapshot suffix is generated:
+/// - `prefix = "..."` is prepended to the computed suffix.
+/// - `separator = "..."` joins the captured `[values]`/`[case]` values (defaults to `-`).
+/// - `suffix = <expr>` is an expression evaluated in the test body that takes precedence over
+///   both the `[values]`/`[case]` derivation and the `description`/`case` fallback. To reach the
+///   context from it, declare a `#[context]` argument and reference that binding.
+/// - `shared` is a bare flag: all cases assert against a single snapshot via
+///   `insta::allow_duplicates!` instead of one suffixed snapshot per case.
+#[derive(Default)]
+pub(crate) struct Args {
+    prefix: Option<LitStr>,
+    separator: Option<LitStr>,
+    suffix: Option<Expr>,
+    shared: bool,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Args::default();
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            match key.to_string().as_str() {
+                "shared" => args.shared = true,
+                "prefix" => {
+                    input.parse::<Token![=]>()?;
+                    args.prefix = Some(input.parse()?);
+                }
+                "separator" => {
+                    input.parse::<Token![=]>()?;
+                    args.separator = Some(input.parse()?);
+                }
+                "suffix" => {
+                    input.parse::<Token![=]>()?;
+                    args.suffix = Some(input.parse()?);
+                }
+                _ => return Err(syn::Error::new(key.span(), "unknown `rstest_insta` argument")),
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok(args)
+    }
+}
+
+pub fn expand(input_fn: ItemFn, args: Args) -> TokenStream {
     let input_fn = expand_attribute(input_fn);
     let (input_fn, ctx_binding) = context_binding(input_fn);
-    let input_fn = expand_body(input_fn, ctx_binding);
+    let value_bindings = value_vars(&input_fn.sig.inputs);
+    let input_fn = expand_body(input_fn, ctx_binding, value_bindings, args);
     input_fn.into_token_stream()
 }
 
@@ -60,19 +112,111 @@ where
         .next()
 }
 
-fn expand_body(mut input_fn: ItemFn, ctx_binding: Ident) -> ItemFn {
+/// Collect the binding idents of arguments annotated with `[values]` or `[case]`.
+///
+/// Matrix tests built from `[values(...)]` lists have no `case` number, so every
+/// permutation would otherwise collapse to the same suffix. The returned idents are
+/// in scope in the generated body once rstest substitutes the concrete values.
+fn value_vars<'a, I>(args: I) -> Vec<Ident>
+where
+    I: IntoIterator<Item = &'a FnArg>,
+{
+    args.into_iter()
+        .filter_map(|arg| match &arg {
+            FnArg::Typed(pat_type) => pat_type
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("values") || attr.path().is_ident("case"))
+                .then_some(pat_type),
+            FnArg::Receiver(_receiver) => None,
+        })
+        .filter_map(|pat_type| match &*pat_type.pat {
+            Pat::Ident(pat_ident) => Some(pat_ident.ident.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn expand_body(
+    mut input_fn: ItemFn,
+    ctx_binding: Ident,
+    value_bindings: Vec<Ident>,
+    args: Args,
+) -> ItemFn {
     const RSTEST_INSTA_SUFFIX: &str = "__rstest_insta__suffix";
     let rstest_insta_suffix = Ident::new(RSTEST_INSTA_SUFFIX, input_fn.span());
     let insta_crate = crate_name("insta");
 
     let fn_block = input_fn.block;
 
-    let body: Block = parse_quote! {
-        {
-            let #rstest_insta_suffix = #ctx_binding.description.map(|s| s.to_string()).unwrap_or(#ctx_binding.case.unwrap_or(0).to_string());
-            #insta_crate::with_settings!({snapshot_suffix => #rstest_insta_suffix}, {
-                #fn_block
-            })
+    // Shared mode: every case asserts against a single snapshot, so no suffix is set and the
+    // body is wrapped in `allow_duplicates!`, which lets the same snapshot name be asserted
+    // repeatedly as long as the values agree.
+    if args.shared {
+        let body: Block = parse_quote! {
+            {
+                #insta_crate::allow_duplicates! {
+                    #fn_block
+                }
+            }
+        };
+        input_fn.block = Box::new(body);
+        return input_fn;
+    }
+
+    // Suffix policy, in order of precedence:
+    // - an explicit `suffix = <expr>` replaces the fallback entirely;
+    // - `[values]`/`[case]` args derive a distinct suffix per permutation, joined by
+    //   `separator` (defaults to `-`);
+    // - otherwise fall back to the `[context]` description/case.
+    let separator = args
+        .separator
+        .unwrap_or_else(|| LitStr::new("-", proc_macro2::Span::call_site()));
+    let base: Expr = if let Some(suffix) = args.suffix {
+        parse_quote!(#suffix)
+    } else if value_bindings.is_empty() {
+        parse_quote! {
+            #ctx_binding.description.map(|s| s.to_string()).unwrap_or(#ctx_binding.case.unwrap_or(0).to_string())
+        }
+    } else {
+        parse_quote! {
+            [#(format!("{:?}", #value_bindings)),*]
+                .iter()
+                .map(|s| s.chars().map(|c| if c.is_alphanumeric() { c } else { '_' }).collect::<String>())
+                .collect::<Vec<_>>()
+                .join(#separator)
+        }
+    };
+
+    // A `prefix` is prepended to whatever the policy produced.
+    let suffix: Expr = match args.prefix {
+        Some(prefix) => parse_quote!(format!("{}{}", #prefix, #base)),
+        None => base,
+    };
+
+    // `with_settings!`'s RAII guard only holds the suffix for the synchronous extent of the
+    // scope. Async tests yield across `.await` and may resume on another thread, losing the
+    // thread-local binding, so they go through `bind_async`, which re-establishes the settings
+    // on every poll.
+    let body: Block = if input_fn.sig.asyncness.is_some() {
+        parse_quote! {
+            {
+                let #rstest_insta_suffix = #suffix;
+                let mut settings = #insta_crate::Settings::clone_current();
+                settings.set_snapshot_suffix(#rstest_insta_suffix);
+                settings.bind_async(async move {
+                    #fn_block
+                }).await
+            }
+        }
+    } else {
+        parse_quote! {
+            {
+                let #rstest_insta_suffix = #suffix;
+                #insta_crate::with_settings!({snapshot_suffix => #rstest_insta_suffix}, {
+                    #fn_block
+                })
+            }
         }
     };
     input_fn.block = Box::new(body);
@@ -145,7 +289,81 @@ mod tests {
         };
 
         // When
-        let expanded = expand(input_fn);
+        let expanded = expand(input_fn, Args::default());
+
+        // Then
+        insta::assert_snapshot!(test_helpers::pretty(expanded));
+    }
+
+    #[test]
+    fn expand_when_args_configure_suffix() {
+        // Given
+        let input_fn: syn::ItemFn = parse_quote! {
+            fn my_test(#[values(1, 2)] a: usize) {
+                println!("Hello, World!")
+            }
+        };
+        let args = Args {
+            prefix: Some(LitStr::new("mod_", proc_macro2::Span::call_site())),
+            separator: Some(LitStr::new("_", proc_macro2::Span::call_site())),
+            suffix: None,
+            shared: false,
+        };
+
+        // When
+        let expanded = expand(input_fn, args);
+
+        // Then
+        insta::assert_snapshot!(test_helpers::pretty(expanded));
+    }
+
+    #[test]
+    fn expand_when_args_are_shared() {
+        // Given
+        let input_fn: syn::ItemFn = parse_quote! {
+            fn my_test(#[values(1, 2)] a: usize) {
+                println!("Hello, World!")
+            }
+        };
+        let args = Args {
+            shared: true,
+            ..Args::default()
+        };
+
+        // When
+        let expanded = expand(input_fn, args);
+
+        // Then
+        insta::assert_snapshot!(test_helpers::pretty(expanded));
+    }
+
+    #[test]
+    fn expand_when_fn_is_async() {
+        // Given
+        let input_fn: syn::ItemFn = parse_quote! {
+            async fn my_test(a: usize) {
+                println!("Hello, World!")
+            }
+        };
+
+        // When
+        let expanded = expand(input_fn, Args::default());
+
+        // Then
+        insta::assert_snapshot!(test_helpers::pretty(expanded));
+    }
+
+    #[test]
+    fn expand_when_fn_contains_value_args() {
+        // Given
+        let input_fn: syn::ItemFn = parse_quote! {
+            fn my_test(#[values(1, 2)] a: usize, #[case] b: &str) {
+                println!("Hello, World!")
+            }
+        };
+
+        // When
+        let expanded = expand(input_fn, Args::default());
 
         // Then
         insta::assert_snapshot!(test_helpers::pretty(expanded));
@@ -5,9 +5,10 @@ use proc_macro::TokenStream;
 use syn::{ItemFn, parse_macro_input};
 
 #[proc_macro_attribute]
-pub fn rstest_insta(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn rstest_insta(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as codegen::Args);
     let input_fn = parse_macro_input!(item as ItemFn);
-    let expanded = codegen::expand(input_fn);
+    let expanded = codegen::expand(input_fn, args);
     TokenStream::from(expanded)
 }
 